@@ -0,0 +1,160 @@
+/// Outcome of [`DocSet::skip_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipResult {
+    /// The cursor landed exactly on the requested doc-id.
+    Reached,
+    /// The cursor landed past the requested doc-id (no posting for it).
+    OverStep,
+    /// The posting list is exhausted; there is no doc-id >= the target.
+    End,
+}
+
+/// A cursor over a sorted stream of doc-ids.
+///
+/// Queries evaluate conjunctions doc-at-a-time: repeatedly take the largest
+/// current doc-id across a set of cursors and [`DocSet::skip_to`] it on the
+/// others, so an intersection leapfrogs over non-matching doc-ids instead of
+/// materializing and sorting a full union. Callers must only `skip_to`
+/// non-decreasing targets; a `DocSet` is not required to handle moving
+/// backwards.
+pub trait DocSet {
+    /// Moves to and returns the next doc-id, or `None` once exhausted.
+    fn advance(&mut self) -> Option<u32>;
+
+    /// Moves forward to the first doc-id >= `target`.
+    fn skip_to(&mut self, target: u32) -> SkipResult;
+
+    /// The doc-id the cursor currently sits on, if the set has been
+    /// advanced at least once and isn't exhausted.
+    fn current(&self) -> Option<u32>;
+}
+
+/// A [`DocSet`] over a term's posting list, sorted and deduplicated on
+/// construction so `skip_to` can binary-search it.
+pub struct TermDocSet {
+    postings: Vec<u32>,
+    pos: Option<usize>,
+}
+
+impl TermDocSet {
+    /// Builds a cursor over `postings`. The input need not already be
+    /// sorted or deduplicated; this constructor does both.
+    pub fn new(postings: &[u32]) -> TermDocSet {
+        let mut postings = postings.to_vec();
+        postings.sort_unstable();
+        postings.dedup();
+        TermDocSet { postings, pos: None }
+    }
+
+    /// Builds a cursor directly over `postings` without sorting or
+    /// deduplicating it. Use this when the caller already has a guarantee
+    /// that the list is sorted and duplicate-free — e.g. a single term's
+    /// posting list straight out of `Index`'s inverted index, which is kept
+    /// in that state at insert time — so a query doesn't pay to re-sort it.
+    pub fn from_sorted(postings: Vec<u32>) -> TermDocSet {
+        TermDocSet { postings, pos: None }
+    }
+}
+
+impl DocSet for TermDocSet {
+    fn advance(&mut self) -> Option<u32> {
+        let next = self.pos.map_or(0, |i| i + 1);
+        self.pos = Some(next);
+        self.current()
+    }
+
+    fn skip_to(&mut self, target: u32) -> SkipResult {
+        let search_from = self.pos.unwrap_or(0);
+        match self.postings[search_from..].binary_search(&target) {
+            Ok(i) => {
+                self.pos = Some(search_from + i);
+                SkipResult::Reached
+            }
+            Err(i) => {
+                let idx = search_from + i;
+                self.pos = Some(idx);
+                if idx >= self.postings.len() {
+                    SkipResult::End
+                } else {
+                    SkipResult::OverStep
+                }
+            }
+        }
+    }
+
+    fn current(&self) -> Option<u32> {
+        self.pos.and_then(|i| self.postings.get(i).copied())
+    }
+}
+
+/// Doc-at-a-time leapfrog intersection of several [`DocSet`]s.
+///
+/// Returns the sorted doc-ids present in every set, without ever
+/// materializing a full union: each round, cursors behind the current
+/// maximum `skip_to` it directly.
+pub(crate) fn intersect(mut sets: Vec<Box<dyn DocSet>>) -> Vec<u32> {
+    if sets.is_empty() {
+        return Vec::new();
+    }
+
+    let mut current: Vec<Option<u32>> = sets.iter_mut().map(|set| set.advance()).collect();
+    let mut results = Vec::new();
+
+    loop {
+        if current.iter().any(Option::is_none) {
+            break;
+        }
+
+        let max = current.iter().map(|doc_id| doc_id.unwrap()).max().unwrap();
+        let mut all_aligned = true;
+
+        for (set, doc_id) in sets.iter_mut().zip(current.iter_mut()) {
+            if doc_id.unwrap() < max {
+                *doc_id = match set.skip_to(max) {
+                    SkipResult::Reached => Some(max),
+                    SkipResult::OverStep | SkipResult::End => {
+                        all_aligned = false;
+                        set.current()
+                    }
+                };
+            }
+        }
+
+        if current.iter().any(Option::is_none) {
+            break;
+        }
+
+        if all_aligned {
+            results.push(max);
+            for (set, doc_id) in sets.iter_mut().zip(current.iter_mut()) {
+                *doc_id = set.advance();
+            }
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_to_reached_and_overstep() {
+        let mut set = TermDocSet::new(&[5, 1, 3, 3, 9]);
+        assert_eq!(set.skip_to(3), SkipResult::Reached);
+        assert_eq!(set.current(), Some(3));
+        assert_eq!(set.skip_to(4), SkipResult::OverStep);
+        assert_eq!(set.current(), Some(5));
+        assert_eq!(set.skip_to(100), SkipResult::End);
+    }
+
+    #[test]
+    fn test_intersect_leapfrogs_to_common_doc_ids() {
+        let a: Box<dyn DocSet> = Box::new(TermDocSet::new(&[1, 2, 3, 5, 8]));
+        let b: Box<dyn DocSet> = Box::new(TermDocSet::new(&[2, 3, 4, 8]));
+        let c: Box<dyn DocSet> = Box::new(TermDocSet::new(&[0, 3, 8, 9]));
+
+        assert_eq!(intersect(vec![a, b, c]), vec![3, 8]);
+    }
+}
@@ -0,0 +1,107 @@
+/// A Levenshtein automaton for bounded edit-distance term matching.
+///
+/// Rather than precompiling a DFA, this walks the classic edit-distance
+/// dynamic-programming table one vocabulary character at a time: each state
+/// is a row of "edits spent aligning the query's first k characters here",
+/// and [`step`](Self::step) produces the next row from the previous one. A
+/// word matches once the final row's last entry is within `max_edits`; for
+/// prefix matching, the word matches as soon as any row along the way has its
+/// last entry within `max_edits`, since a later suffix no longer matters.
+pub struct LevenshteinAutomaton {
+    query: Vec<char>,
+    max_edits: usize,
+}
+
+impl LevenshteinAutomaton {
+    pub fn new(query: &str, max_edits: usize) -> LevenshteinAutomaton {
+        LevenshteinAutomaton {
+            query: query.chars().collect(),
+            max_edits,
+        }
+    }
+
+    pub(crate) fn initial_row(&self) -> Vec<usize> {
+        (0..=self.query.len()).collect()
+    }
+
+    /// Produces the next DP row after consuming vocabulary character `c`.
+    pub(crate) fn step(&self, row: &[usize], c: char) -> Vec<usize> {
+        let mut next = Vec::with_capacity(row.len());
+        next.push(row[0] + 1);
+        for (i, &query_char) in self.query.iter().enumerate() {
+            let substitution_cost = if query_char == c { 0 } else { 1 };
+            let value = (row[i] + substitution_cost)
+                .min(row[i + 1] + 1)
+                .min(next[i] + 1);
+            next.push(value);
+        }
+        next
+    }
+
+    /// Whether `row` itself already represents a match (used for both the
+    /// leaf check in [`is_match`](Self::is_match) and the every-row check in
+    /// [`is_prefix_match`](Self::is_prefix_match)).
+    pub(crate) fn row_matches(&self, row: &[usize]) -> bool {
+        row.last().is_some_and(|&edits| edits <= self.max_edits)
+    }
+
+    /// Whether `row` proves that no word continuing this prefix can ever
+    /// match within `max_edits`, so callers walking a sorted vocabulary can
+    /// stop extending this branch instead of stepping through every
+    /// remaining character. Sound because a DP row's minimum entry can only
+    /// stay the same or grow as more vocabulary characters are consumed.
+    pub(crate) fn can_prune(&self, row: &[usize]) -> bool {
+        row.iter().min().is_some_and(|&edits| edits > self.max_edits)
+    }
+
+    /// Whether `word` is within `max_edits` of the query term.
+    pub fn is_match(&self, word: &str) -> bool {
+        let mut row = self.initial_row();
+        for c in word.chars() {
+            row = self.step(&row, c);
+        }
+        self.row_matches(&row)
+    }
+
+    /// Whether some prefix of `word` is within `max_edits` of the query
+    /// term, so the last token of a query can complete a longer indexed
+    /// term (e.g. `"inter"` prefix-matching `"internet"`).
+    ///
+    /// Unlike [`is_match`](Self::is_match), this checks the last row entry
+    /// after every character consumed, not just the final one: the query is
+    /// the thing being matched in full, against a growing prefix of `word`.
+    pub fn is_prefix_match(&self, word: &str) -> bool {
+        let mut row = self.initial_row();
+        if self.row_matches(&row) {
+            return true;
+        }
+        for c in word.chars() {
+            row = self.step(&row, c);
+            if self.row_matches(&row) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_match_within_edit_distance() {
+        let automaton = LevenshteinAutomaton::new("cats", 1);
+        assert!(automaton.is_match("cats"));
+        assert!(automaton.is_match("cat"));
+        assert!(automaton.is_match("bats"));
+        assert!(!automaton.is_match("dogs"));
+    }
+
+    #[test]
+    fn test_is_prefix_match() {
+        let automaton = LevenshteinAutomaton::new("inter", 1);
+        assert!(automaton.is_prefix_match("internet"));
+        assert!(!automaton.is_prefix_match("banana"));
+    }
+}
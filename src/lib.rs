@@ -1,5 +1,19 @@
+mod docset;
+mod levenshtein;
+mod persistence;
+mod preprocessing;
+mod proximity;
+mod query;
+
+pub use docset::{DocSet, SkipResult, TermDocSet};
+pub use levenshtein::LevenshteinAutomaton;
+pub use preprocessing::{Language, Preprocessor, PreprocessorConfig};
+pub use query::{parse_query, Op, QueryParseError};
+
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashSet};
+use std::io;
+use std::path::Path;
 use rustc_hash::FxHashMap;
 use ordered_float::OrderedFloat;
 
@@ -8,6 +22,9 @@ pub struct DocumentStats {
     doc_id: u32,
     doc_length: u32,
     term_freq: FxHashMap<String, u32>,
+    /// Word offsets (within the processed term stream) at which each term
+    /// occurs, in ascending order. Backs phrase and proximity queries.
+    term_positions: FxHashMap<String, Vec<u32>>,
 }
 
 #[derive(Debug, Clone)]
@@ -15,24 +32,43 @@ pub struct Index {
     inverted_index: FxHashMap<String, Vec<u32>>,
     doc_stats: FxHashMap<u32, DocumentStats>,
     total_doc_lengths: u32,
-    k: f64,
+    preprocessor: Preprocessor,
+    k1: f64,
     b: f64,
 }
 
 impl Index {
-    pub fn new() -> Index {
+    pub fn new(config: PreprocessorConfig) -> Index {
         Index {
             inverted_index: FxHashMap::default(),
             doc_stats: FxHashMap::default(),
             total_doc_lengths: 0,
-            k: 1.5,
+            preprocessor: Preprocessor::new(config),
+            k1: 1.5,
             b: 0.75,
         }
     }
 
+    /// Builds an index with BM25's `k1` (term-frequency saturation) and `b`
+    /// (length-normalization strength) tuned for a specific corpus, instead
+    /// of the defaults `k1 = 1.5`, `b = 0.75`.
+    pub fn with_params(config: PreprocessorConfig, k1: f64, b: f64) -> Index {
+        Index {
+            k1,
+            b,
+            ..Index::new(config)
+        }
+    }
+
+    /// Inserts `doc_id` into each term's posting list, keeping the list
+    /// sorted and deduplicated as it goes so `TermDocSet` can trust it and
+    /// skip re-sorting on every query.
     fn update_inverted_index(&mut self, terms: &[String], doc_id: u32) {
         for term in terms {
-            self.inverted_index.entry(term.clone()).or_insert_with(Vec::new).push(doc_id);
+            let postings = self.inverted_index.entry(term.clone()).or_default();
+            if let Err(pos) = postings.binary_search(&doc_id) {
+                postings.insert(pos, doc_id);
+            }
         }
     }
 
@@ -40,6 +76,45 @@ impl Index {
         self.inverted_index.get(term).map_or(0, |ids| ids.len() as u32)
     }
 
+    /// How many documents `term` occurs in, after the same preprocessing
+    /// used at search time.
+    pub fn term_doc_frequency(&self, term: &str) -> u32 {
+        self.preprocessor
+            .process(term)
+            .first()
+            .map_or(0, |term| self.doc_frequency(term))
+    }
+
+    /// The `limit` terms with the highest document frequency, descending
+    /// (ties broken alphabetically). Useful for spotting stopword
+    /// candidates or a skewed term distribution before committing to a
+    /// [`PreprocessorConfig`].
+    pub fn most_common_terms(&self, limit: usize) -> Vec<(String, u32)> {
+        let mut terms: Vec<(String, u32)> = self
+            .inverted_index
+            .iter()
+            .map(|(term, ids)| (term.clone(), ids.len() as u32))
+            .collect();
+        terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        terms.truncate(limit);
+        terms
+    }
+
+    /// The number of documents currently in the index.
+    pub fn num_documents(&self) -> usize {
+        self.doc_stats.len()
+    }
+
+    /// The average document length, in processed terms, across the index.
+    /// `0.0` for an empty index.
+    pub fn average_doc_length(&self) -> f64 {
+        if self.doc_stats.is_empty() {
+            0.0
+        } else {
+            self.total_doc_lengths as f64 / self.doc_stats.len() as f64
+        }
+    }
+
     fn term_frequency(&self, terms: &[String]) -> FxHashMap<String, u32> {
         let mut term_freq = FxHashMap::default();
         for term in terms {
@@ -48,6 +123,26 @@ impl Index {
         term_freq
     }
 
+    fn term_positions(terms: &[String]) -> FxHashMap<String, Vec<u32>> {
+        let mut term_positions: FxHashMap<String, Vec<u32>> = FxHashMap::default();
+        for (position, term) in terms.iter().enumerate() {
+            term_positions.entry(term.clone()).or_default().push(position as u32);
+        }
+        term_positions
+    }
+
+    /// Persists this index to `dir` as a handful of flat files, so a large
+    /// corpus can be built once and reloaded with [`Index::load`] instead of
+    /// re-indexing from scratch every run.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        persistence::save(self, dir.as_ref())
+    }
+
+    /// Reloads an index previously written with [`Index::save`].
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Index> {
+        persistence::load(dir.as_ref())
+    }
+
     pub fn upsert(&mut self, doc: &str, doc_id: u32) {
         if self.doc_stats.contains_key(&doc_id) {
             self.delete(doc_id);
@@ -56,17 +151,23 @@ impl Index {
     }
 
     fn insert(&mut self, doc: &str, doc_id: u32) {
-        let mut terms = tokenize(doc);
-        terms = stemmer(&terms).to_vec();
+        let terms = self.preprocessor.process(doc);
         let num_terms = terms.len();
         let term_freq = self.term_frequency(&terms);
-        self.update_inverted_index(&terms, doc_id);
+        let term_positions = Self::term_positions(&terms);
+        // Record each doc-id once per term, regardless of in-doc repeat
+        // count: `term_freq` above is where repeat counts live, so a
+        // posting list's length is a document frequency, not a raw
+        // occurrence count.
+        let unique_terms: Vec<String> = term_freq.keys().cloned().collect();
+        self.update_inverted_index(&unique_terms, doc_id);
         self.doc_stats.insert(
             doc_id,
             DocumentStats {
                 doc_id,
                 doc_length: num_terms as u32,
                 term_freq,
+                term_positions,
             },
         );
         self.total_doc_lengths += num_terms as u32;
@@ -75,7 +176,7 @@ impl Index {
     pub fn delete(&mut self, doc_id: u32) {
         if let Some(doc) = self.doc_stats.remove(&doc_id) {
             self.total_doc_lengths -= doc.doc_length;
-            for (term, freq) in doc.term_freq {
+            for term in doc.term_freq.into_keys() {
                 if let Some(ids) = self.inverted_index.get_mut(&term) {
                     ids.retain(|&id| id != doc_id);
                 }
@@ -84,9 +185,7 @@ impl Index {
     }
 
     pub fn search(&self, query: &str, top_k: u32) -> Vec<(OrderedFloat<f64>, u32)> {
-        let query_terms: Vec<String> = tokenize(query).into_iter().map(|t| stemmer(&[t])[0].clone()).collect();
-        let avg_doc_length = self.total_doc_lengths as f64 / self.doc_stats.len() as f64;
-        let num_docs = self.doc_stats.len() as f64;
+        let query_terms: Vec<String> = self.preprocessor.process(query);
 
         let mut doc_ids = Vec::new();
         for term in &query_terms {
@@ -98,26 +197,295 @@ impl Index {
         doc_ids.sort_unstable();
         doc_ids.dedup();
 
+        let weighted_terms: Vec<(String, f64)> = query_terms.into_iter().map(|t| (t, 1.0)).collect();
+        self.rank(&weighted_terms, doc_ids, top_k, |_| 0.0)
+    }
+
+    /// Evaluates a parsed boolean query tree against the inverted index to
+    /// restrict the candidate document set, then ranks the survivors with
+    /// the same BM25 formula as [`Index::search`]. Terms negated by `Not`
+    /// only narrow the candidate set; they don't contribute to the score.
+    pub fn search_boolean(&self, query_tree: &Op, top_k: u32) -> Vec<(OrderedFloat<f64>, u32)> {
+        let mut doc_ids: Vec<u32> = self.eval_op(query_tree).into_iter().collect();
+        doc_ids.sort_unstable();
+
+        let mut raw_terms = Vec::new();
+        query_tree.collect_score_terms(&mut raw_terms);
+        let weighted_terms: Vec<(String, f64)> = raw_terms
+            .iter()
+            .flat_map(|term| self.preprocessor.process(term))
+            .map(|term| (term, 1.0))
+            .collect();
+
+        self.rank(&weighted_terms, doc_ids, top_k, |_| 0.0)
+    }
+
+    /// Requires every term in `phrase` to occur adjacently and in order,
+    /// e.g. `"machine learning"` only matches where those two (processed)
+    /// tokens sit at consecutive positions — not just anywhere in the same
+    /// document. Surviving documents are ranked by the usual BM25 formula.
+    pub fn search_phrase(&self, phrase: &str, top_k: u32) -> Vec<(OrderedFloat<f64>, u32)> {
+        let terms = self.preprocessor.process(phrase);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut doc_ids = Vec::new();
+        'docs: for (doc_id, doc) in &self.doc_stats {
+            let mut position_lists = Vec::with_capacity(terms.len());
+            for term in &terms {
+                match doc.term_positions.get(term) {
+                    Some(positions) => position_lists.push(positions),
+                    None => continue 'docs,
+                }
+            }
+            if proximity::has_adjacent_phrase(&position_lists) {
+                doc_ids.push(*doc_id);
+            }
+        }
+        doc_ids.sort_unstable();
+
+        let weighted_terms: Vec<(String, f64)> = terms.into_iter().map(|t| (t, 1.0)).collect();
+        self.rank(&weighted_terms, doc_ids, top_k, |_| 0.0)
+    }
+
+    /// Ranks documents containing every query term by BM25 blended with a
+    /// proximity bonus inversely proportional to the width of the smallest
+    /// window that covers all of them, so documents where the terms cluster
+    /// tightly together outrank ones where they're scattered far apart.
+    pub fn search_proximity(&self, query: &str, top_k: u32) -> Vec<(OrderedFloat<f64>, u32)> {
+        const PROXIMITY_WEIGHT: f64 = 1.0;
+
+        let terms = self.preprocessor.process(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let doc_sets: Vec<Box<dyn DocSet>> = terms
+            .iter()
+            .filter_map(|term| self.inverted_index.get(term))
+            .map(|postings| Box::new(TermDocSet::from_sorted(postings.clone())) as Box<dyn DocSet>)
+            .collect();
+        let doc_ids = if doc_sets.len() == terms.len() {
+            docset::intersect(doc_sets)
+        } else {
+            Vec::new() // some term is missing from the vocabulary entirely
+        };
+
+        let weighted_terms: Vec<(String, f64)> = terms.iter().cloned().map(|t| (t, 1.0)).collect();
+        self.rank(&weighted_terms, doc_ids, top_k, |doc| {
+            let position_lists: Vec<&Vec<u32>> =
+                terms.iter().filter_map(|term| doc.term_positions.get(term)).collect();
+            match proximity::minimal_window(&position_lists) {
+                Some(width) => PROXIMITY_WEIGHT / (width as f64 + 1.0),
+                None => 0.0,
+            }
+        })
+    }
+
+    /// Walks `automaton` across `vocabulary` (sorted) rather than testing
+    /// each term from scratch: consecutive terms share a prefix, so the DP
+    /// rows computed for one term are reused for however much prefix the
+    /// next term shares with it, and a branch whose row can no longer
+    /// produce a match is abandoned without stepping through its remaining
+    /// characters.
+    fn fuzzy_matches<'a>(
+        vocabulary: &[&'a String],
+        automaton: &LevenshteinAutomaton,
+        match_as_prefix: bool,
+    ) -> Vec<&'a String> {
+        let mut matches = Vec::new();
+        let mut rows = vec![automaton.initial_row()];
+        let mut prev_chars: Vec<char> = Vec::new();
+
+        for &candidate in vocabulary {
+            let chars: Vec<char> = candidate.chars().collect();
+            let shared = chars.iter().zip(prev_chars.iter()).take_while(|(a, b)| a == b).count();
+            // Clamp to what's actually been computed: if the previous
+            // candidate's branch was abandoned early (pruned), the shared
+            // prefix beyond that point was never materialized, but its last
+            // row already proves that prefix can't match, so treat it as
+            // the effective shared length instead of indexing past it.
+            let shared = shared.min(rows.len() - 1);
+            rows.truncate(shared + 1);
+
+            let mut matched_as_prefix =
+                match_as_prefix && rows.iter().any(|row| automaton.row_matches(row));
+            let mut pruned = false;
+            for &c in &chars[shared..] {
+                let last_row = rows.last().unwrap();
+                if automaton.can_prune(last_row) {
+                    pruned = true;
+                    break;
+                }
+                let next_row = automaton.step(last_row, c);
+                if match_as_prefix && automaton.row_matches(&next_row) {
+                    matched_as_prefix = true;
+                }
+                rows.push(next_row);
+            }
+
+            prev_chars = chars;
+
+            // Abandoning the branch only means no *longer* continuation can
+            // match; a shorter prefix already found to match earlier in
+            // this word (e.g. "cat" inside "caterpillar") is still valid.
+            if pruned && !matched_as_prefix {
+                continue;
+            }
+            let is_whole_word_match = !pruned && automaton.row_matches(rows.last().unwrap());
+            let is_match = if match_as_prefix { matched_as_prefix } else { is_whole_word_match };
+            if is_match {
+                matches.push(candidate);
+            }
+        }
+
+        matches
+    }
+
+    /// Spelling-tolerant search: each query token matches any indexed term
+    /// within `max_edits` (via a [`LevenshteinAutomaton`]), and the
+    /// surviving terms' posting lists are unioned before BM25 scoring.
+    /// Fuzzy matches (edit distance > 0) are down-weighted relative to an
+    /// exact match so a typo-laden query doesn't outrank a clean one. When
+    /// `prefix` is set, the last token is prefix-matched instead (so `"inter"`
+    /// can complete `"internet"`) while earlier tokens still require a
+    /// whole-term match.
+    pub fn search_fuzzy(
+        &self,
+        query: &str,
+        top_k: u32,
+        max_edits: usize,
+        prefix: bool,
+    ) -> Vec<(OrderedFloat<f64>, u32)> {
+        const FUZZY_MATCH_WEIGHT: f64 = 0.5;
+
+        let query_terms = self.preprocessor.process(query);
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        // Sorted so `fuzzy_matches` can walk shared prefixes of the
+        // vocabulary instead of testing every term independently.
+        let mut vocabulary: Vec<&String> = self.inverted_index.keys().collect();
+        vocabulary.sort();
+
+        let mut doc_ids = Vec::new();
+        let mut weighted_terms = Vec::new();
+        let last_term_index = query_terms.len() - 1;
+
+        for (i, query_term) in query_terms.iter().enumerate() {
+            let automaton = LevenshteinAutomaton::new(query_term, max_edits);
+            let match_as_prefix = prefix && i == last_term_index;
+
+            for candidate in Self::fuzzy_matches(&vocabulary, &automaton, match_as_prefix) {
+                if let Some(ids) = self.inverted_index.get(candidate.as_str()) {
+                    doc_ids.extend_from_slice(ids);
+                }
+                let weight = if candidate.as_str() == query_term {
+                    1.0
+                } else {
+                    FUZZY_MATCH_WEIGHT
+                };
+                weighted_terms.push((candidate.clone(), weight));
+            }
+        }
+
+        doc_ids.sort_unstable();
+        doc_ids.dedup();
+
+        self.rank(&weighted_terms, doc_ids, top_k, |_| 0.0)
+    }
+
+    fn eval_op(&self, op: &Op) -> HashSet<u32> {
+        match op {
+            Op::Term(term) => {
+                let mut matches = HashSet::new();
+                for term in self.preprocessor.process(term) {
+                    if let Some(ids) = self.inverted_index.get(&term) {
+                        matches.extend(ids.iter().copied());
+                    }
+                }
+                matches
+            }
+            // Fast path: a conjunction of bare terms can leapfrog doc-ids via
+            // `DocSet::skip_to` instead of materializing each operand as a
+            // `HashSet` and intersecting them.
+            Op::And(ops) if ops.iter().all(|op| matches!(op, Op::Term(_))) => {
+                let doc_sets: Vec<Box<dyn DocSet>> = ops
+                    .iter()
+                    .map(|op| {
+                        let Op::Term(term) = op else { unreachable!() };
+                        let mut postings = Vec::new();
+                        for token in self.preprocessor.process(term) {
+                            if let Some(ids) = self.inverted_index.get(&token) {
+                                postings.extend_from_slice(ids);
+                            }
+                        }
+                        Box::new(TermDocSet::new(&postings)) as Box<dyn DocSet>
+                    })
+                    .collect();
+                docset::intersect(doc_sets).into_iter().collect()
+            }
+            Op::And(ops) => {
+                let mut sets = ops.iter().map(|op| self.eval_op(op));
+                let Some(first) = sets.next() else {
+                    return HashSet::new();
+                };
+                sets.fold(first, |acc, set| acc.intersection(&set).copied().collect())
+            }
+            Op::Or(ops) => ops.iter().fold(HashSet::new(), |mut acc, op| {
+                acc.extend(self.eval_op(op));
+                acc
+            }),
+            Op::Not(inner) => {
+                let excluded = self.eval_op(inner);
+                self.doc_stats
+                    .keys()
+                    .copied()
+                    .filter(|doc_id| !excluded.contains(doc_id))
+                    .collect()
+            }
+        }
+    }
+
+    /// Ranks `doc_ids` by BM25 against `weighted_terms`, each paired with a
+    /// multiplier applied to that term's contribution to the score (`1.0`
+    /// for an ordinary match; < `1.0` to discount e.g. a fuzzy match), plus
+    /// whatever `bonus` adds on top (e.g. a proximity bonus) for each
+    /// surviving document.
+    fn rank(
+        &self,
+        weighted_terms: &[(String, f64)],
+        doc_ids: Vec<u32>,
+        top_k: u32,
+        bonus: impl Fn(&DocumentStats) -> f64,
+    ) -> Vec<(OrderedFloat<f64>, u32)> {
+        let avg_doc_length = self.total_doc_lengths as f64 / self.doc_stats.len() as f64;
+        let num_docs = self.doc_stats.len() as f64;
+
         let mut top_k_docs = BinaryHeap::new();
 
         for doc_id in doc_ids {
             if let Some(doc) = self.doc_stats.get(&doc_id) {
                 let doc_length = doc.doc_length as f64;
-                let length_norm = self.k * ((1.0 - self.b) + self.b * doc_length / avg_doc_length);
+                let length_norm = self.k1 * ((1.0 - self.b) + self.b * doc_length / avg_doc_length);
                 let mut score = 0.0;
 
-                for term in &query_terms {
+                for (term, weight) in weighted_terms {
                     if let Some(&term_freq) = doc.term_freq.get(term) {
                         let term_freq = term_freq as f64;
                         let doc_freq = self.doc_frequency(term) as f64;
                         if doc_freq > 0.0 {
-                            let tf = term_freq / (length_norm + term_freq);
+                            let tf = (term_freq * (self.k1 + 1.0)) / (term_freq + length_norm);
                             let idf = ((num_docs - doc_freq + 0.5) / (doc_freq + 0.5) + 1.0).ln();
-                            score += tf * idf;
+                            score += weight * tf * idf;
                         }
                     }
                 }
 
+                score += bonus(doc);
+
                 if top_k_docs.len() < top_k as usize {
                     top_k_docs.push(Reverse((OrderedFloat(score), doc_id)));
                 } else if let Some(&Reverse((lowest_score, _))) = top_k_docs.peek() {
@@ -144,10 +512,6 @@ pub fn tokenize(doc: &str) -> Vec<String> {
     doc.split_whitespace().map(|s| s.to_string()).collect()
 }
 
-pub fn stemmer(words: &[String]) -> &[String] {
-    words
-}
-
 
 #[cfg(test)]
 mod tests {
@@ -160,16 +524,9 @@ mod tests {
         assert_eq!(tokens, vec!["Hello", "world"]);
     }
 
-    #[test]
-    fn test_stemmer() {
-        let words = vec!["like".to_string(), "likes".to_string()];
-        let stemmed = stemmer(&words);
-        assert_eq!(stemmed, vec!["like", "likes"]);
-    }
-
     #[test]
     fn test_term_frequency() {
-        let index = Index::new();
+        let index = Index::new(PreprocessorConfig::default());
         let terms = vec!["like".to_string(), "like".to_string(), "cats".to_string()];
         let term_freq = index.term_frequency(&terms);
         assert_eq!(term_freq.get("like"), Some(&2));
@@ -178,18 +535,28 @@ mod tests {
 
     #[test]
     fn test_insert() {
-        let mut index = Index::new();
+        let mut index = Index::new(PreprocessorConfig::default());
         index.insert("Hello world", 0);
         index.insert("I like like cats", 1);
         index.insert("I like dogs", 2);
 
-        assert_eq!(index.inverted_index.get("like"), Some(&vec![1, 1, 2]));
+        assert_eq!(index.inverted_index.get("like"), Some(&vec![1, 2]));
         assert_eq!(index.doc_stats.len(), 3);
     }
 
+    #[test]
+    fn test_insert_keeps_posting_lists_sorted_out_of_order() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("I like cats", 5);
+        index.insert("I like cats", 1);
+        index.insert("I like cats", 3);
+
+        assert_eq!(index.inverted_index.get("cat"), Some(&vec![1, 3, 5]));
+    }
+
     #[test]
     fn test_search() {
-        let mut index = Index::new();
+        let mut index = Index::new(PreprocessorConfig::default());
         index.insert("Hello world", 123);
         index.insert("I like like cats", 456);
         index.insert("I like dogs", 789);
@@ -199,4 +566,129 @@ mod tests {
         assert_eq!(results[0].1, 456);
         assert_eq!(results[1].1, 789);
     }
+
+    #[test]
+    fn test_search_boolean_and_not() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("I like cats", 1);
+        index.insert("I like cats and dogs", 2);
+        index.insert("I like dogs", 3);
+
+        let query_tree = parse_query("cats AND NOT dogs").unwrap();
+        let results = index.search_boolean(&query_tree, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, 1);
+    }
+
+    #[test]
+    fn test_search_boolean_and_terms_uses_docset_fast_path() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("I like cats", 1);
+        index.insert("I like cats and dogs", 2);
+        index.insert("I like dogs", 3);
+
+        let query_tree = parse_query("cats AND dogs").unwrap();
+        let results = index.search_boolean(&query_tree, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, 2);
+    }
+
+    #[test]
+    fn test_search_fuzzy_matches_typo() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("I like cats", 1);
+        index.insert("I like dogs", 2);
+
+        let results = index.search_fuzzy("cet", 10, 1, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, 1);
+    }
+
+    #[test]
+    fn test_search_fuzzy_skips_unrelated_vocabulary_branches() {
+        // A vocabulary with a long run of terms sharing no prefix with the
+        // query exercises the shared-prefix walk's pruning: those terms
+        // should never reach a full automaton step without being abandoned.
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("cats", 1);
+        index.insert("catastrophe catalog category", 2);
+        index.insert("dogs ducks dingoes", 3);
+        index.insert("zebras zeppelins", 4);
+
+        let results = index.search_fuzzy("cet", 10, 1, false);
+        let matched_docs: HashSet<u32> = results.iter().map(|&(_, doc_id)| doc_id).collect();
+        assert_eq!(matched_docs, HashSet::from([1]));
+    }
+
+    #[test]
+    fn test_search_fuzzy_prefix_completes_last_token() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("I like caterpillars", 1);
+        index.insert("I like dogs", 2);
+
+        let results = index.search_fuzzy("cat", 10, 0, true);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, 1);
+    }
+
+    #[test]
+    fn test_search_phrase_requires_adjacency() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("the cat sat on the mat", 1);
+        index.insert("the mat sat on the cat", 2);
+
+        let results = index.search_phrase("cat sat", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, 1);
+    }
+
+    #[test]
+    fn test_search_proximity_favors_tighter_clusters() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("cat sat mat", 1);
+        index.insert("cat dog fish bird mat", 2);
+
+        let results = index.search_proximity("cat mat", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].1, 1);
+    }
+
+    #[test]
+    fn test_with_params_changes_ranking() {
+        let mut default_index = Index::new(PreprocessorConfig::default());
+        default_index.insert("cats are great and cats are fun", 1);
+        default_index.insert("cats", 2);
+
+        let mut flat_index = Index::with_params(PreprocessorConfig::default(), 0.0, 0.0);
+        flat_index.insert("cats are great and cats are fun", 1);
+        flat_index.insert("cats", 2);
+
+        // With k1 = 0, the tf component is 1 for any non-zero term frequency,
+        // so neither the repeat count nor the document length affects the
+        // score: both documents score the same, while the defaults give the
+        // longer, repeat-heavy document (tf 2) and the short one (tf 1)
+        // different scores.
+        let default_scores = default_index.search("cats", 10);
+        let flat_scores = flat_index.search("cats", 10);
+        assert_ne!(default_scores[0].0, default_scores[1].0);
+        assert_eq!(flat_scores[0].0, flat_scores[1].0);
+    }
+
+    #[test]
+    fn test_corpus_statistics() {
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.insert("cat sat mat", 1);
+        index.insert("cat dog fish bird mat", 2);
+
+        assert_eq!(index.num_documents(), 2);
+        assert_eq!(index.average_doc_length(), 4.0);
+        assert_eq!(index.term_doc_frequency("cat"), 2);
+        assert_eq!(index.term_doc_frequency("dog"), 1);
+        assert_eq!(index.term_doc_frequency("nonexistent"), 0);
+
+        let most_common = index.most_common_terms(2);
+        assert_eq!(most_common.len(), 2);
+        assert_eq!(most_common[0], ("cat".to_string(), 2));
+        assert_eq!(most_common[1].1, 2);
+    }
 }
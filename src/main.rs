@@ -1,4 +1,4 @@
-use bm25_rs::Index;
+use bm25_rs::{Index, PreprocessorConfig};
 
 use std::collections::HashMap;
 use std::fs::File;
@@ -42,7 +42,7 @@ fn main() {
         // get initial memory usage
         let initial_memory_usage = measure_memory().unwrap_or(0);
 
-        let mut index = Index::new();
+        let mut index = Index::new(PreprocessorConfig::default());
         
         // Insert documents
         let insert_start_time = Instant::now();
@@ -0,0 +1,274 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use crate::preprocessing::{Language, PreprocessorConfig};
+use crate::{DocumentStats, Index, Preprocessor};
+
+const MAGIC: &[u8; 4] = b"BM25";
+const VERSION: u8 = 2;
+
+/// Writes an [`Index`] to `dir` as four files:
+/// - `vocab.bin`: sorted terms with their byte offset into `postings.bin`.
+/// - `postings.bin`: delta-encoded, sorted doc-id posting lists.
+/// - `docstats.bin`: per-document length, term frequencies and positions.
+/// - `params.bin`: the BM25 `k1`/`b` parameters and preprocessor config.
+pub(crate) fn save(index: &Index, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let mut vocab: Vec<&String> = index.inverted_index.keys().collect();
+    vocab.sort();
+    let term_ids: FxHashMap<&str, u32> = vocab
+        .iter()
+        .enumerate()
+        .map(|(i, term)| (term.as_str(), i as u32))
+        .collect();
+
+    let mut vocab_buf = Vec::new();
+    let mut postings_buf = Vec::new();
+    for term in &vocab {
+        let mut doc_ids = index.inverted_index[term.as_str()].clone();
+        doc_ids.sort_unstable();
+
+        let offset = postings_buf.len() as u64;
+        write_varint(&mut postings_buf, doc_ids.len() as u64);
+        let mut prev = 0u32;
+        for doc_id in &doc_ids {
+            write_varint(&mut postings_buf, (doc_id - prev) as u64);
+            prev = *doc_id;
+        }
+
+        write_varint(&mut vocab_buf, term.len() as u64);
+        vocab_buf.extend_from_slice(term.as_bytes());
+        vocab_buf.extend_from_slice(&offset.to_le_bytes());
+    }
+
+    let mut doc_ids: Vec<&u32> = index.doc_stats.keys().collect();
+    doc_ids.sort_unstable();
+    let mut docstats_buf = Vec::new();
+    write_varint(&mut docstats_buf, doc_ids.len() as u64);
+    for doc_id in doc_ids {
+        let doc = &index.doc_stats[doc_id];
+        docstats_buf.extend_from_slice(&doc.doc_id.to_le_bytes());
+        docstats_buf.extend_from_slice(&doc.doc_length.to_le_bytes());
+        write_varint(&mut docstats_buf, doc.term_freq.len() as u64);
+        for (term, freq) in &doc.term_freq {
+            docstats_buf.extend_from_slice(&term_ids[term.as_str()].to_le_bytes());
+            write_varint(&mut docstats_buf, *freq as u64);
+
+            let positions = doc.term_positions.get(term).map(Vec::as_slice).unwrap_or(&[]);
+            write_varint(&mut docstats_buf, positions.len() as u64);
+            let mut prev = 0u32;
+            for &position in positions {
+                write_varint(&mut docstats_buf, (position - prev) as u64);
+                prev = position;
+            }
+        }
+    }
+
+    let mut params_buf = Vec::new();
+    params_buf.extend_from_slice(MAGIC);
+    params_buf.push(VERSION);
+    params_buf.extend_from_slice(&index.k1.to_le_bytes());
+    params_buf.extend_from_slice(&index.b.to_le_bytes());
+    params_buf.extend_from_slice(&index.total_doc_lengths.to_le_bytes());
+    write_preprocessor_config(&mut params_buf, index.preprocessor.config());
+
+    fs::write(dir.join("vocab.bin"), vocab_buf)?;
+    fs::write(dir.join("postings.bin"), postings_buf)?;
+    fs::write(dir.join("docstats.bin"), docstats_buf)?;
+    fs::write(dir.join("params.bin"), params_buf)?;
+    Ok(())
+}
+
+/// Reloads an [`Index`] previously written by [`save`].
+pub(crate) fn load(dir: &Path) -> io::Result<Index> {
+    let vocab_buf = fs::read(dir.join("vocab.bin"))?;
+    let postings_buf = fs::read(dir.join("postings.bin"))?;
+    let docstats_buf = fs::read(dir.join("docstats.bin"))?;
+    let params_buf = fs::read(dir.join("params.bin"))?;
+
+    if params_buf.len() < 5 || &params_buf[0..4] != MAGIC {
+        return Err(invalid_data("not a bm25-rs index"));
+    }
+    if params_buf[4] != VERSION {
+        return Err(invalid_data("unsupported index version"));
+    }
+    let mut pos = 5;
+    let k1 = read_f64(&params_buf, &mut pos)?;
+    let b = read_f64(&params_buf, &mut pos)?;
+    let total_doc_lengths = read_u32(&params_buf, &mut pos)?;
+    let config = read_preprocessor_config(&params_buf, &mut pos)?;
+
+    let mut vocab = Vec::new();
+    let mut pos = 0;
+    while pos < vocab_buf.len() {
+        let len = read_varint(&vocab_buf, &mut pos)? as usize;
+        let term_bytes = slice(&vocab_buf, pos, len)?;
+        let term = String::from_utf8(term_bytes.to_vec()).map_err(|e| invalid_data(&e.to_string()))?;
+        pos += len;
+        let offset = u64::from_le_bytes(slice(&vocab_buf, pos, 8)?.try_into().unwrap());
+        pos += 8;
+        vocab.push((term, offset));
+    }
+
+    let mut inverted_index = FxHashMap::default();
+    for (term, offset) in &vocab {
+        let mut pos = *offset as usize;
+        let count = read_varint(&postings_buf, &mut pos)?;
+        let mut doc_ids = Vec::with_capacity(count as usize);
+        let mut prev = 0u32;
+        for _ in 0..count {
+            prev += read_varint(&postings_buf, &mut pos)? as u32;
+            doc_ids.push(prev);
+        }
+        inverted_index.insert(term.clone(), doc_ids);
+    }
+
+    let mut doc_stats = FxHashMap::default();
+    let mut pos = 0;
+    let num_docs = read_varint(&docstats_buf, &mut pos)?;
+    for _ in 0..num_docs {
+        let doc_id = read_u32(&docstats_buf, &mut pos)?;
+        let doc_length = read_u32(&docstats_buf, &mut pos)?;
+        let num_terms = read_varint(&docstats_buf, &mut pos)?;
+        let mut term_freq = FxHashMap::default();
+        let mut term_positions = FxHashMap::default();
+        for _ in 0..num_terms {
+            let term_id = read_u32(&docstats_buf, &mut pos)? as usize;
+            let freq = read_varint(&docstats_buf, &mut pos)? as u32;
+            let term = vocab
+                .get(term_id)
+                .ok_or_else(|| invalid_data("term id out of range"))?
+                .0
+                .clone();
+
+            let num_positions = read_varint(&docstats_buf, &mut pos)?;
+            let mut positions = Vec::with_capacity(num_positions as usize);
+            let mut prev = 0u32;
+            for _ in 0..num_positions {
+                prev += read_varint(&docstats_buf, &mut pos)? as u32;
+                positions.push(prev);
+            }
+
+            term_freq.insert(term.clone(), freq);
+            term_positions.insert(term, positions);
+        }
+        doc_stats.insert(
+            doc_id,
+            DocumentStats {
+                doc_id,
+                doc_length,
+                term_freq,
+                term_positions,
+            },
+        );
+    }
+
+    Ok(Index {
+        inverted_index,
+        doc_stats,
+        total_doc_lengths,
+        preprocessor: Preprocessor::new(config),
+        k1,
+        b,
+    })
+}
+
+fn write_preprocessor_config(buf: &mut Vec<u8>, config: PreprocessorConfig) {
+    let flags = (config.lowercase as u8)
+        | (config.strip_punctuation as u8) << 1
+        | (config.remove_stopwords as u8) << 2
+        | (config.stem as u8) << 3;
+    buf.push(flags);
+    buf.push(config.language.to_byte());
+}
+
+fn read_preprocessor_config(buf: &[u8], pos: &mut usize) -> io::Result<PreprocessorConfig> {
+    let flags = *slice(buf, *pos, 1)?.first().unwrap();
+    let language_byte = *slice(buf, *pos + 1, 1)?.first().unwrap();
+    *pos += 2;
+    let language = Language::from_byte(language_byte).ok_or_else(|| invalid_data("unknown language byte"))?;
+    Ok(PreprocessorConfig {
+        lowercase: flags & 0b0001 != 0,
+        strip_punctuation: flags & 0b0010 != 0,
+        remove_stopwords: flags & 0b0100 != 0,
+        stem: flags & 0b1000 != 0,
+        language,
+    })
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> io::Result<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *slice(buf, *pos, 1)?.first().unwrap();
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(value)
+}
+
+fn read_u32(buf: &[u8], pos: &mut usize) -> io::Result<u32> {
+    let value = u32::from_le_bytes(slice(buf, *pos, 4)?.try_into().unwrap());
+    *pos += 4;
+    Ok(value)
+}
+
+fn read_f64(buf: &[u8], pos: &mut usize) -> io::Result<f64> {
+    let value = f64::from_le_bytes(slice(buf, *pos, 8)?.try_into().unwrap());
+    *pos += 8;
+    Ok(value)
+}
+
+fn slice(buf: &[u8], start: usize, len: usize) -> io::Result<&[u8]> {
+    buf.get(start..start + len).ok_or_else(|| invalid_data("unexpected end of file"))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PreprocessorConfig;
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join("bm25_rs_persistence_test_roundtrip");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut index = Index::new(PreprocessorConfig::default());
+        index.upsert("I like cats", 1);
+        index.upsert("I like dogs", 2);
+
+        save(&index, &dir).unwrap();
+        let reloaded = load(&dir).unwrap();
+
+        assert_eq!(reloaded.search("cats", 10), index.search("cats", 10));
+        assert_eq!(reloaded.search("like", 10), index.search("like", 10));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
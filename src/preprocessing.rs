@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// Languages with a curated stopword list and a matching Snowball stemmer.
+///
+/// Only the languages listed here are supported; each maps to its own
+/// stopword list and Snowball [`Algorithm`], and stopword removal is only as
+/// good as the list shipped for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    pub(crate) fn to_byte(self) -> u8 {
+        match self {
+            Language::English => 0,
+            Language::French => 1,
+            Language::German => 2,
+            Language::Spanish => 3,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Language> {
+        match byte {
+            0 => Some(Language::English),
+            1 => Some(Language::French),
+            2 => Some(Language::German),
+            3 => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    fn algorithm(self) -> Algorithm {
+        match self {
+            Language::English => Algorithm::English,
+            Language::French => Algorithm::French,
+            Language::German => Algorithm::German,
+            Language::Spanish => Algorithm::Spanish,
+        }
+    }
+
+    fn stopwords(self) -> &'static [&'static str] {
+        match self {
+            Language::English => &[
+                "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "he", "i",
+                "in", "is", "it", "its", "of", "on", "that", "the", "to", "was", "were", "will",
+                "with",
+            ],
+            Language::French => &[
+                "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et",
+                "eux", "il", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "même",
+                "mes", "moi", "mon", "ne", "nos", "notre", "nous", "on", "ou", "par", "pas",
+                "pour", "qu", "que", "qui", "sa", "se", "ses", "son", "sur", "ta", "te", "tes",
+                "toi", "ton", "tu", "un", "une", "vos", "votre", "vous",
+            ],
+            Language::German => &[
+                "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "bin", "bist", "da",
+                "damit", "dann", "der", "die", "das", "dass", "dein", "deine", "dem", "den",
+                "des", "doch", "dort", "du", "durch", "ein", "eine", "einem", "einen", "einer",
+                "eines", "er", "es", "euer", "eure", "für", "hatte", "hatten", "hier", "ich",
+                "ihr", "ihre", "im", "in", "ist", "ja", "jede", "jedem", "jeden", "jeder",
+                "jedes", "jener", "jetzt", "kann", "kein", "keine", "können", "man", "mein",
+                "meine", "mit", "muss", "nach", "nein", "nicht", "noch", "nun", "nur", "ob",
+                "oder", "sehr", "sein", "seine", "sich", "sie", "sind", "so", "über", "um",
+                "und", "uns", "unser", "unter", "viel", "vom", "von", "vor", "war", "waren",
+                "warst", "was", "weiter", "welche", "welcher", "wenn", "werde", "werden", "wie",
+                "wieder", "will", "wir", "wird", "wirst", "wo", "zu", "zum", "zur",
+            ],
+            Language::Spanish => &[
+                "como", "con", "de", "del", "el", "ella", "ellos", "en", "era", "es", "esa",
+                "ese", "esta", "este", "fue", "ha", "han", "hay", "la", "las", "le", "lo", "los",
+                "más", "me", "mi", "mis", "mucho", "muy", "no", "nos", "nosotros", "o", "para",
+                "pero", "por", "que", "se", "si", "sin", "sobre", "son", "su", "sus", "te",
+                "tiene", "tu", "tus", "un", "una", "unas", "uno", "unos", "y", "ya", "yo",
+            ],
+        }
+    }
+}
+
+/// Which normalization stages a [`Preprocessor`] runs, in order.
+///
+/// Every stage defaults to enabled; disable a stage when the corpus already
+/// satisfies it (e.g. pre-lowercased data) or when a caller wants to inspect
+/// the effect of stemming/stopwords in isolation.
+#[derive(Debug, Clone, Copy)]
+pub struct PreprocessorConfig {
+    pub lowercase: bool,
+    pub strip_punctuation: bool,
+    pub remove_stopwords: bool,
+    pub stem: bool,
+    pub language: Language,
+}
+
+impl Default for PreprocessorConfig {
+    fn default() -> Self {
+        PreprocessorConfig {
+            lowercase: true,
+            strip_punctuation: true,
+            remove_stopwords: true,
+            stem: true,
+            language: Language::English,
+        }
+    }
+}
+
+/// Normalizes raw text into index terms.
+///
+/// `Index` runs every document and every query through the same
+/// `Preprocessor` so that indexing and querying agree on what a "term" is.
+#[derive(Debug, Clone)]
+pub struct Preprocessor {
+    config: PreprocessorConfig,
+    stopwords: HashSet<&'static str>,
+}
+
+impl Preprocessor {
+    pub fn new(config: PreprocessorConfig) -> Preprocessor {
+        let stopwords = if config.remove_stopwords {
+            config.language.stopwords().iter().copied().collect()
+        } else {
+            HashSet::new()
+        };
+        Preprocessor { config, stopwords }
+    }
+
+    /// Returns the configuration this preprocessor was built with, so a
+    /// persisted `Index` can be reloaded with identical normalization.
+    pub(crate) fn config(&self) -> PreprocessorConfig {
+        self.config
+    }
+
+    /// Tokenizes and normalizes `text` into index terms.
+    pub fn process(&self, text: &str) -> Vec<String> {
+        crate::tokenize(text)
+            .into_iter()
+            .filter_map(|token| self.process_token(&token))
+            .collect()
+    }
+
+    fn process_token(&self, token: &str) -> Option<String> {
+        let mut term = token.to_string();
+
+        if self.config.lowercase {
+            term = term.to_lowercase();
+        }
+
+        if self.config.strip_punctuation {
+            term.retain(|c| !c.is_ascii_punctuation());
+        }
+
+        if term.is_empty() {
+            return None;
+        }
+
+        if self.config.remove_stopwords && self.stopwords.contains(term.as_str()) {
+            return None;
+        }
+
+        if self.config.stem {
+            let stemmer = Stemmer::create(self.config.language.algorithm());
+            term = stemmer.stem(&term).into_owned();
+        }
+
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_lowercases_and_stems() {
+        let preprocessor = Preprocessor::new(PreprocessorConfig::default());
+        assert_eq!(
+            preprocessor.process("Cats, and Dogs."),
+            vec!["cat".to_string(), "dog".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_process_disable_stages() {
+        let config = PreprocessorConfig {
+            lowercase: false,
+            strip_punctuation: false,
+            remove_stopwords: false,
+            stem: false,
+            language: Language::English,
+        };
+        let preprocessor = Preprocessor::new(config);
+        assert_eq!(
+            preprocessor.process("The Cats."),
+            vec!["The".to_string(), "Cats.".to_string()]
+        );
+    }
+}
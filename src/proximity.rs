@@ -0,0 +1,100 @@
+/// Whether the given per-term position lists contain a run of consecutive
+/// positions `start, start + 1, ..., start + lists.len() - 1` — one from
+/// each list in order — i.e. whether the terms occur as an exact phrase.
+///
+/// `position_lists[i]` must be the sorted word-offsets of the i-th phrase
+/// token within the document.
+pub(crate) fn has_adjacent_phrase(position_lists: &[&Vec<u32>]) -> bool {
+    let Some(first) = position_lists.first() else {
+        return false;
+    };
+
+    'starts: for &start in first.iter() {
+        for (i, positions) in position_lists.iter().enumerate().skip(1) {
+            if positions.binary_search(&(start + i as u32)).is_err() {
+                continue 'starts;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+/// The width of the smallest window that contains at least one position
+/// from every list in `position_lists`, or `None` if any list is empty.
+///
+/// This is the classic "smallest range covering one element from each of k
+/// sorted lists" problem: merge every `(position, list_index)` pair and
+/// slide a window over it, shrinking from the left whenever every list is
+/// still represented.
+pub(crate) fn minimal_window(position_lists: &[&Vec<u32>]) -> Option<u32> {
+    let num_lists = position_lists.len();
+    if num_lists == 0 || position_lists.iter().any(|positions| positions.is_empty()) {
+        return None;
+    }
+
+    let mut entries: Vec<(u32, usize)> = position_lists
+        .iter()
+        .enumerate()
+        .flat_map(|(list_idx, positions)| positions.iter().map(move |&pos| (pos, list_idx)))
+        .collect();
+    entries.sort_unstable();
+
+    let mut counts = vec![0u32; num_lists];
+    let mut distinct_lists_in_window = 0;
+    let mut left = 0;
+    let mut best_width = None;
+
+    for right in 0..entries.len() {
+        let (_, list_idx) = entries[right];
+        if counts[list_idx] == 0 {
+            distinct_lists_in_window += 1;
+        }
+        counts[list_idx] += 1;
+
+        while distinct_lists_in_window == num_lists {
+            let width = entries[right].0 - entries[left].0;
+            best_width = Some(best_width.map_or(width, |best: u32| best.min(width)));
+
+            let (_, left_list_idx) = entries[left];
+            counts[left_list_idx] -= 1;
+            if counts[left_list_idx] == 0 {
+                distinct_lists_in_window -= 1;
+            }
+            left += 1;
+        }
+    }
+
+    best_width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_adjacent_phrase() {
+        let machine = vec![0, 5];
+        let learning = vec![1, 9];
+        assert!(has_adjacent_phrase(&[&machine, &learning]));
+
+        let learning_far = vec![9];
+        assert!(!has_adjacent_phrase(&[&machine, &learning_far]));
+    }
+
+    #[test]
+    fn test_minimal_window() {
+        let a = vec![0, 10];
+        let b = vec![3, 11];
+        let c = vec![2, 12];
+
+        assert_eq!(minimal_window(&[&a, &b, &c]), Some(2));
+    }
+
+    #[test]
+    fn test_minimal_window_empty_list_is_none() {
+        let a = vec![0];
+        let b: Vec<u32> = vec![];
+        assert_eq!(minimal_window(&[&a, &b]), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+
+/// A parsed boolean query tree.
+///
+/// Built by [`parse_query`] and evaluated by [`crate::Index::search_boolean`]
+/// against the inverted index before BM25 scoring: `And`/`Or`/`Not` restrict
+/// the candidate document set, then the surviving documents are ranked as
+/// usual.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op {
+    And(Vec<Op>),
+    Or(Vec<Op>),
+    Not(Box<Op>),
+    Term(String),
+}
+
+impl Op {
+    /// Collects every term that should contribute to BM25 scoring, i.e.
+    /// every `Term` not nested under a `Not` (negated terms only restrict
+    /// the candidate set, they shouldn't boost a document's score).
+    pub(crate) fn collect_score_terms(&self, out: &mut Vec<String>) {
+        match self {
+            Op::And(ops) | Op::Or(ops) => {
+                for op in ops {
+                    op.collect_score_terms(out);
+                }
+            }
+            Op::Not(_) => {}
+            Op::Term(term) => out.push(term.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParseError(String);
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse boolean query: {}", self.0)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+/// Parses a boolean query string into an [`Op`] tree.
+///
+/// Supports `AND`, `OR`, `NOT` (case-sensitive keywords) and parenthesized
+/// grouping. Terms placed next to each other with no keyword between them
+/// default to `OR`, matching the behavior of the plain bag-of-terms
+/// `Index::search`.
+pub fn parse_query(query: &str) -> Result<Op, QueryParseError> {
+    let tokens = tokenize_query(query);
+    if tokens.is_empty() {
+        return Err(QueryParseError("empty query".to_string()));
+    }
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+    let op = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(QueryParseError(format!(
+            "unexpected token `{}`",
+            parser.tokens[parser.pos]
+        )));
+    }
+    Ok(op)
+}
+
+fn tokenize_query(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in query.chars() {
+        if c == '(' || c == ')' {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            tokens.push(c.to_string());
+        } else if c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn advance(&mut self) -> Option<&str> {
+        let token = self.tokens.get(self.pos).map(String::as_str);
+        self.pos += 1;
+        token
+    }
+
+    // or_expr := and_expr (OR? and_expr)*
+    fn parse_or(&mut self) -> Result<Op, QueryParseError> {
+        let mut terms = vec![self.parse_and()?];
+        loop {
+            match self.peek() {
+                Some("OR") => {
+                    self.advance();
+                    terms.push(self.parse_and()?);
+                }
+                Some(")") | None => break,
+                Some("AND") => break,
+                Some(_) => terms.push(self.parse_and()?),
+            }
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Op::Or(terms) })
+    }
+
+    // and_expr := not_expr (AND not_expr)*
+    fn parse_and(&mut self) -> Result<Op, QueryParseError> {
+        let mut terms = vec![self.parse_not()?];
+        while self.peek() == Some("AND") {
+            self.advance();
+            terms.push(self.parse_not()?);
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Op::And(terms) })
+    }
+
+    // not_expr := NOT? primary
+    fn parse_not(&mut self) -> Result<Op, QueryParseError> {
+        if self.peek() == Some("NOT") {
+            self.advance();
+            return Ok(Op::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    // primary := TERM | '(' or_expr ')'
+    fn parse_primary(&mut self) -> Result<Op, QueryParseError> {
+        match self.advance() {
+            Some("(") => {
+                let inner = self.parse_or()?;
+                match self.advance() {
+                    Some(")") => Ok(inner),
+                    _ => Err(QueryParseError("unmatched `(`".to_string())),
+                }
+            }
+            Some(")") => Err(QueryParseError("unexpected `)`".to_string())),
+            Some(token) => Ok(Op::Term(token.to_string())),
+            None => Err(QueryParseError("unexpected end of query".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_term() {
+        assert_eq!(parse_query("cats").unwrap(), Op::Term("cats".to_string()));
+    }
+
+    #[test]
+    fn test_parse_implicit_or() {
+        assert_eq!(
+            parse_query("cats dogs").unwrap(),
+            Op::Or(vec![Op::Term("cats".to_string()), Op::Term("dogs".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_parse_and_not() {
+        assert_eq!(
+            parse_query("cats AND NOT dogs").unwrap(),
+            Op::And(vec![
+                Op::Term("cats".to_string()),
+                Op::Not(Box::new(Op::Term("dogs".to_string()))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_parens() {
+        assert_eq!(
+            parse_query("(cats OR dogs) AND NOT fish").unwrap(),
+            Op::And(vec![
+                Op::Or(vec![Op::Term("cats".to_string()), Op::Term("dogs".to_string())]),
+                Op::Not(Box::new(Op::Term("fish".to_string()))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_unmatched_paren_errors() {
+        assert!(parse_query("(cats AND dogs").is_err());
+    }
+}